@@ -0,0 +1,41 @@
+use rodio::{source::SineWave, OutputStream, OutputStreamHandle, Sink, Source};
+
+/// The CHIP-8 buzzer: a single 440 Hz tone, built once at startup and
+/// played/paused in place rather than rebuilt every time `sound_timer` ticks.
+pub struct Audio {
+    _stream: OutputStream,
+    _stream_handle: OutputStreamHandle,
+    sink: Sink,
+    beeping: bool,
+}
+
+impl Audio {
+    pub fn new() -> Self {
+        let (stream, stream_handle) = rodio::OutputStream::try_default().unwrap();
+        let sink = Sink::try_new(&stream_handle).unwrap();
+        sink.append(SineWave::new(440.0).repeat_infinite());
+        sink.pause();
+
+        Audio {
+            _stream: stream,
+            _stream_handle: stream_handle,
+            sink,
+            beeping: false,
+        }
+    }
+
+    /// Starts or stops the tone on a `false -> true` / `true -> false`
+    /// transition; a repeated call with the same value is a no-op.
+    pub fn set_beeping(&mut self, on: bool) {
+        if on == self.beeping {
+            return;
+        }
+        self.beeping = on;
+
+        if on {
+            self.sink.play();
+        } else {
+            self.sink.pause();
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::display::Display;
+use crate::keypad::Keypad;
+use crate::memory::Memory;
+
+/// Everything the `Cpu` reaches out to while executing an instruction:
+/// addressable memory, the framebuffer, and the keypad. Kept separate from
+/// `Cpu` so the instruction decoder stays agnostic of how these peripherals
+/// are actually presented to the user.
+pub struct Bus {
+    pub memory: Memory,
+    pub display: Display,
+    pub keypad: Keypad,
+}
+
+impl Bus {
+    pub fn new() -> Self {
+        Bus {
+            memory: Memory::new(),
+            display: Display::new(),
+            keypad: Keypad::new(),
+        }
+    }
+}
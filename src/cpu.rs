@@ -0,0 +1,344 @@
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+use crate::bus::Bus;
+
+/// Toggles for the well-known ambiguous CHIP-8 instructions.
+///
+/// Different interpreters (and the original COSMAC VIP) disagree on the
+/// behavior of a handful of opcodes. `Quirks` lets a `Cpu` be configured
+/// to match whichever convention a given ROM expects, instead of hard-coding
+/// one interpretation.
+#[derive(Clone, Copy, Debug)]
+pub struct Quirks {
+    /// `8XY6`/`8XYE`: if `true`, copy `v[y]` into `v[x]` before shifting
+    /// (COSMAC VIP behavior). If `false`, shift `v[x]` in place (modern/CHIP-48).
+    pub shift: bool,
+    /// `FX55`/`FX65`: if `true`, leave `I` as-is after the load/store. If
+    /// `false`, increment `I` by `X + 1` afterward (classic COSMAC VIP behavior).
+    pub load_store: bool,
+    /// `BNNN`: if `true`, jump to `NNN + v[0]` (modern/CHIP-48). If `false`,
+    /// jump to `NNN + v[X]` where `X` is the high nibble of `NNN` (COSMAC VIP).
+    pub jump: bool,
+    /// `8XY1`/`8XY2`/`8XY3`: if `true`, zero `VF` after the logical op
+    /// (COSMAC VIP behavior).
+    pub vf_reset: bool,
+    /// `DXYN`: if `true`, clip sprites at the screen edges. If `false`,
+    /// wrap them around to the opposite edge.
+    pub clipping: bool,
+}
+
+impl Quirks {
+    /// Matches the original COSMAC VIP interpreter.
+    pub const COSMAC_VIP: Quirks = Quirks {
+        shift: true,
+        load_store: false,
+        jump: false,
+        vf_reset: true,
+        clipping: true,
+    };
+
+    /// Matches most modern interpreters (CHIP-48 / SUPER-CHIP derived).
+    #[allow(dead_code)]
+    pub const MODERN: Quirks = Quirks {
+        shift: false,
+        load_store: true,
+        jump: true,
+        vf_reset: false,
+        clipping: true,
+    };
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Quirks::COSMAC_VIP
+    }
+}
+
+/// The CHIP-8 execution core: registers, stack, and the fetch/decode/execute
+/// loop. Talks to memory, the display, and the keypad only through a `Bus`,
+/// so it has no dependency on how those peripherals are actually presented.
+pub struct Cpu {
+    pub pc: u16,
+    pub v: [u8; 16],
+    pub i: u16,
+    pub stack: [u16; 16],
+    pub sp: u8,
+    pub delay_timer: u8,
+    pub sound_timer: u8,
+    pub quirks: Quirks,
+    /// Set by SUPER-CHIP's `00FD` ("exit"); the frontend should stop running.
+    pub halted: bool,
+    rng: ThreadRng,
+}
+
+impl Cpu {
+    pub fn new(quirks: Quirks) -> Self {
+        Cpu {
+            pc: 0x200,
+            v: [0; 16],
+            i: 0,
+            stack: [0; 16],
+            sp: 0,
+            delay_timer: 0,
+            sound_timer: 0,
+            quirks,
+            halted: false,
+            rng: rand::thread_rng(),
+        }
+    }
+
+    fn jmp(&mut self, target: u16) {
+        self.pc = target;
+    }
+
+    fn jsr(&mut self, target: u16) {
+        self.stack[self.sp as usize] = self.pc;
+        self.sp = (self.sp + 1) % 16;
+        self.pc = target;
+    }
+
+    fn rts(&mut self) {
+        let (result, overflow) = self.sp.overflowing_sub(1);
+
+        if overflow {
+            self.sp = 15;
+        } else {
+            self.sp = result;
+        }
+
+        self.pc = self.stack[self.sp as usize];
+    }
+
+    fn skc(&mut self, condition: bool) {
+        if condition {
+            self.pc += 2;
+        }
+    }
+
+    /// Fetches the opcode at `pc` without advancing it.
+    pub fn fetch(&self, bus: &Bus) -> u16 {
+        let instr_high = bus.memory.mem[self.pc as usize] as u16;
+        let instr_low = bus.memory.mem[(self.pc + 1) as usize] as u16;
+        (instr_high << 8) | instr_low
+    }
+
+    /// Fetches, decodes, and executes exactly one instruction, returning the
+    /// opcode that was executed (for tracing/history purposes).
+    pub fn step(&mut self, bus: &mut Bus) -> u16 {
+        let opcode = self.fetch(bus);
+        self.execute(opcode, bus);
+        opcode
+    }
+
+    /// Decodes and executes a previously fetched opcode.
+    pub fn execute(&mut self, opcode: u16, bus: &mut Bus) {
+        let instr_high: u8 = (opcode >> 8) as u8;
+        let instr_low: u8 = (opcode & 0xFF) as u8;
+        self.pc += 2;
+
+        let x: u8 = instr_high & 0x0F;
+        let y: u8 = (instr_low & 0xF0) >> 4;
+        let z: u8 = instr_low & 0x0F;
+        let nnn: u16 = (x as u16) << 8 | (instr_low as u16);
+
+        match (instr_high & 0xF0) >> 4 {
+            0x0 => {
+                match instr_low {
+                    0xE0 => bus.display.clear(),
+                    0xEE => self.rts(),
+                    0xFB => bus.display.scroll_right(),
+                    0xFC => bus.display.scroll_left(),
+                    0xFD => self.halted = true,
+                    0xFE => bus.display.set_hires(false),
+                    0xFF => bus.display.set_hires(true),
+                    n if (n & 0xF0) == 0xC0 => bus.display.scroll_down((n & 0x0F) as usize),
+                    _ => panic!("Illegal Opcode in ROM")
+                }
+            },
+            0x1 => self.jmp(nnn),
+            0x2 => self.jsr(nnn),
+            0x3 => self.skc(self.v[x as usize] == instr_low),
+            0x4 => self.skc(self.v[x as usize] != instr_low),
+            0x5 => self.skc(self.v[x as usize] == self.v[y as usize]),
+            0x6 => self.v[x as usize] = instr_low,
+            0x7 => self.v[x as usize] = self.v[x as usize].wrapping_add(instr_low),
+            0x8 => {
+                match z {
+                    0x0 => self.v[x as usize] = self.v[y as usize],
+                    0x1 => {
+                        self.v[x as usize] |= self.v[y as usize];
+                        if self.quirks.vf_reset {
+                            self.v[0xF] = 0;
+                        }
+                    },
+                    0x2 => {
+                        self.v[x as usize] &= self.v[y as usize];
+                        if self.quirks.vf_reset {
+                            self.v[0xF] = 0;
+                        }
+                    },
+                    0x3 => {
+                        self.v[x as usize] ^= self.v[y as usize];
+                        if self.quirks.vf_reset {
+                            self.v[0xF] = 0;
+                        }
+                    },
+                    0x4 => {
+                        let vx = self.v[x as usize];
+                        let vy = self.v[y as usize];
+
+                        let (result, overflow) = vx.overflowing_add(vy);
+
+                        self.v[x as usize] = result;
+                        self.v[0xF] = if overflow {1} else {0};
+                    },
+                    0x5 => {
+                        let vx = self.v[x as usize];
+                        let vy = self.v[y as usize];
+
+                        let (result, overflow) = vx.overflowing_sub(vy);
+
+                        self.v[x as usize] = result;
+                        self.v[0xF] = if overflow {1} else {0};
+                    },
+                    0x6 => {
+                        let src = if self.quirks.shift { self.v[y as usize] } else { self.v[x as usize] };
+                        self.v[0xF] = src & 0b00000001;
+                        self.v[x as usize] = src >> 1;
+                    },
+                    0x7 => {
+                        let vx = self.v[x as usize];
+                        let vy = self.v[y as usize];
+
+                        let (result, overflow) = vy.overflowing_sub(vx);
+
+                        self.v[x as usize] = result;
+                        self.v[0xF] = if overflow {1} else {0};
+                    },
+                    0xE => {
+                        let src = if self.quirks.shift { self.v[y as usize] } else { self.v[x as usize] };
+                        self.v[0xF] = (src & 0b10000000) >> 7;
+                        self.v[x as usize] = src << 1;
+                    },
+                    _ => panic!("Illegal Opcode in ROM")
+                }
+            },
+            0x9 => self.skc(self.v[x as usize] != self.v[y as usize]),
+            0xA => self.i = nnn,
+            0xB => {
+                let offset = if self.quirks.jump { self.v[0] } else { self.v[x as usize] };
+                self.jmp((offset as u16) + nnn)
+            },
+            0xC => self.v[x as usize] = self.rng.gen::<u8>() & instr_low,
+            0xD => {
+                let collision = if z == 0 {
+                    // SUPER-CHIP DXY0: 16x16 sprite, 2 bytes per row.
+                    let mut rows = [0u16; 16];
+                    for (row, slot) in rows.iter_mut().enumerate() {
+                        let addr = (self.i as usize) + row * 2;
+                        *slot = ((bus.memory.mem[addr] as u16) << 8) | (bus.memory.mem[addr + 1] as u16);
+                    }
+                    bus.display.draw_sprite_16(self.v[x as usize], self.v[y as usize], &rows, self.quirks.clipping)
+                } else {
+                    let sprite = &bus.memory.mem[(self.i as usize)..(self.i as usize) + (z as usize)];
+                    bus.display.draw_sprite(self.v[x as usize], self.v[y as usize], sprite, self.quirks.clipping)
+                };
+                self.v[0xF] = if collision { 1 } else { 0 };
+            },
+            0xE => {
+                match instr_low {
+                    0x9E => self.skc(bus.keypad.is_pressed(self.v[x as usize])),
+                    0xA1 => self.skc(!bus.keypad.is_pressed(self.v[x as usize])),
+                    _ => panic!("Illegal Opcode in ROM")
+                }
+            },
+            0xF => {
+                match instr_low {
+                    0x07 => self.v[x as usize] = self.delay_timer,
+                    0x0A => {
+                        match bus.keypad.wait_for_key() {
+                            Some(code) => self.v[x as usize] = code,
+                            None => self.pc -= 2,
+                        }
+                    },
+                    0x15 => self.delay_timer = self.v[x as usize],
+                    0x18 => self.sound_timer = self.v[x as usize],
+                    0x1E => self.i += self.v[x as usize] as u16,
+                    0x29 => self.i = 0x50 + (5 * self.v[x as usize] as u16),
+                    0x30 => self.i = crate::memory::BIG_FONT_START as u16 + (10 * self.v[x as usize] as u16),
+                    0x33 => {
+                        let mut hex_num: u8 = self.v[x as usize];
+                        bus.memory.mem[(self.i + 2) as usize] = hex_num % 10;
+                        hex_num /= 10;
+                        bus.memory.mem[(self.i + 1) as usize] = hex_num % 10;
+                        hex_num /= 10;
+                        bus.memory.mem[(self.i) as usize] = hex_num % 10;
+                    },
+                    0x55 => {
+                        for i in 0..=x {
+                            bus.memory.mem[(self.i as usize) + (i as usize)] = self.v[i as usize];
+                        }
+                        if !self.quirks.load_store {
+                            self.i += x as u16 + 1;
+                        }
+                    },
+                    0x65 => {
+                        for i in 0..=x {
+                            self.v[i as usize] = bus.memory.mem[(self.i as usize) + (i as usize)];
+                        }
+                        if !self.quirks.load_store {
+                            self.i += x as u16 + 1;
+                        }
+                    },
+                    _ => panic!("Illegal Opcode in ROM")
+                }
+            }
+            _ => panic!("Illegal Opcode in ROM: {:#01x}", (instr_high & 0xF0) >> 4)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+
+    fn cpu_bus(quirks: Quirks) -> (Cpu, Bus) {
+        (Cpu::new(quirks), Bus::new())
+    }
+
+    #[test]
+    fn skc_skips_on_vx_equal_vy() {
+        let (mut cpu, mut bus) = cpu_bus(Quirks::default());
+        cpu.v[1] = 5;
+        cpu.v[2] = 5;
+        let pc = cpu.pc;
+
+        cpu.execute(0x5120, &mut bus);
+
+        assert_eq!(cpu.pc, pc + 4, "5XY0 should skip the next instruction when v[x] == v[y]");
+    }
+
+    #[test]
+    fn eight_xy6_shift_quirk_reads_vy() {
+        let (mut cpu, mut bus) = cpu_bus(Quirks::COSMAC_VIP);
+        cpu.v[1] = 0;
+        cpu.v[2] = 0b0000_0011;
+
+        cpu.execute(0x8126, &mut bus);
+
+        assert_eq!(cpu.v[1], 0b0000_0001, "COSMAC_VIP shift quirk should shift v[y], not v[x]");
+        assert_eq!(cpu.v[0xF], 1);
+    }
+
+    #[test]
+    fn seven_xnn_add_wraps_instead_of_panicking() {
+        let (mut cpu, mut bus) = cpu_bus(Quirks::default());
+        cpu.v[0] = 0xFF;
+
+        cpu.execute(0x7002, &mut bus);
+
+        assert_eq!(cpu.v[0], 1);
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use crate::cpu::Cpu;
+
+/// Number of recent (pc, opcode) pairs kept for post-mortem inspection.
+const HISTORY_CAPACITY: usize = 64;
+
+/// Stepping debugger for the emulator loop: a bounded trace of recently
+/// executed instructions, address breakpoints, and an interactive
+/// pause/step/continue/dump mode driven by the frontend.
+pub struct Debugger {
+    /// Gates the per-instruction `{pc, opcode}` trace printed to stdout.
+    pub tracing: bool,
+    /// When `true`, the frontend should stop calling `Machine::step` and
+    /// instead wait for single-step/continue input.
+    pub paused: bool,
+    /// PC values that, when hit, automatically set `paused`.
+    pub breakpoints: Vec<u16>,
+    history: VecDeque<(u16, u16)>,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Debugger {
+            tracing: false,
+            paused: false,
+            breakpoints: Vec::new(),
+            history: VecDeque::with_capacity(HISTORY_CAPACITY),
+        }
+    }
+
+    /// Records one fetched instruction and pauses if it hit a breakpoint.
+    pub fn record(&mut self, pc: u16, opcode: u16) {
+        if self.tracing {
+            println!("{:#06x}: {:#06x}", pc, opcode);
+        }
+
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back((pc, opcode));
+
+        if self.breakpoints.contains(&pc) {
+            self.paused = true;
+        }
+    }
+
+    /// Dumps CPU registers, the stack, timers, and recent PC/opcode history.
+    pub fn dump(&self, cpu: &Cpu) {
+        println!("--- debugger dump ---");
+        println!("pc: {:#06x}  i: {:#06x}  sp: {}", cpu.pc, cpu.i, cpu.sp);
+        println!("delay_timer: {}  sound_timer: {}", cpu.delay_timer, cpu.sound_timer);
+        for (reg, value) in cpu.v.iter().enumerate() {
+            println!("v{:X}: {:#04x}", reg, value);
+        }
+        println!("stack: {:04x?}", cpu.stack);
+        println!("history (oldest first):");
+        for (pc, opcode) in self.history.iter() {
+            println!("  {:#06x}: {:#06x}", pc, opcode);
+        }
+        println!("---------------------");
+    }
+}
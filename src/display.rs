@@ -0,0 +1,190 @@
+/// Low-res (original CHIP-8) screen dimensions.
+pub const LORES_WIDTH: usize = 64;
+pub const LORES_HEIGHT: usize = 32;
+/// Hi-res (SUPER-CHIP) screen dimensions.
+pub const HIRES_WIDTH: usize = 128;
+pub const HIRES_HEIGHT: usize = 64;
+
+/// The largest physical pixel grid a frontend ever needs to allocate; used
+/// to size the window/backing buffer regardless of the current resolution.
+pub const MAX_WIDTH: usize = HIRES_WIDTH;
+pub const MAX_HEIGHT: usize = HIRES_HEIGHT;
+
+/// The CHIP-8 / SUPER-CHIP framebuffer and sprite-drawing logic. Switches
+/// between the 64x32 low-res grid and the 128x64 SUPER-CHIP hi-res grid via
+/// `set_hires`; low-res pixels are rendered as 2x2 blocks so callers can
+/// always target a fixed `MAX_WIDTH x MAX_HEIGHT` buffer.
+pub struct Display {
+    hires: bool,
+    pixels: Vec<Vec<bool>>,
+}
+
+impl Display {
+    pub fn new() -> Self {
+        Display {
+            hires: false,
+            pixels: vec![vec![false; LORES_WIDTH]; LORES_HEIGHT],
+        }
+    }
+
+    pub fn is_hires(&self) -> bool {
+        self.hires
+    }
+
+    pub fn width(&self) -> usize {
+        if self.hires { HIRES_WIDTH } else { LORES_WIDTH }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.hires { HIRES_HEIGHT } else { LORES_HEIGHT }
+    }
+
+    /// Switches resolution (`00FE`/`00FF`) and clears the screen, matching
+    /// how SUPER-CHIP interpreters handle the mode switch.
+    pub fn set_hires(&mut self, hires: bool) {
+        self.hires = hires;
+        self.pixels = vec![vec![false; self.width()]; self.height()];
+    }
+
+    pub fn clear(&mut self) {
+        self.pixels = vec![vec![false; self.width()]; self.height()];
+    }
+
+    /// Scrolls the screen down by `n` pixels (`00CN`).
+    pub fn scroll_down(&mut self, n: usize) {
+        let height = self.height();
+        self.pixels.rotate_right(n.min(height));
+        for row in self.pixels.iter_mut().take(n.min(height)) {
+            row.fill(false);
+        }
+    }
+
+    /// Scrolls the screen right by 4 pixels (`00FB`).
+    pub fn scroll_right(&mut self) {
+        for row in self.pixels.iter_mut() {
+            row.rotate_right(4);
+            row[..4].fill(false);
+        }
+    }
+
+    /// Scrolls the screen left by 4 pixels (`00FC`).
+    pub fn scroll_left(&mut self) {
+        for row in self.pixels.iter_mut() {
+            let width = row.len();
+            row.rotate_left(4);
+            row[width - 4..].fill(false);
+        }
+    }
+
+    /// Draws an 8-pixel-wide sprite (one byte per row) at `(x, y)` and
+    /// reports whether any pixel was toggled off (collision), per `DXYN`.
+    pub fn draw_sprite(&mut self, x: u8, y: u8, sprite: &[u8], clipping: bool) -> bool {
+        let width = self.width();
+        let height = self.height();
+        let mut collision = false;
+        let mut y_disp: usize = (y as usize) % height;
+
+        for &sprite_byte in sprite {
+            let mut bit: u8 = 0b10000000;
+            let mut x_disp: usize = (x as usize) % width;
+
+            while bit > 0 {
+                if sprite_byte & bit != 0 {
+                    collision |= self.toggle(x_disp, y_disp);
+                }
+                bit >>= 1;
+                x_disp += 1;
+                if x_disp == width {
+                    if clipping {
+                        break;
+                    }
+                    x_disp = 0;
+                }
+            }
+            y_disp += 1;
+            if y_disp == height {
+                if clipping {
+                    break;
+                }
+                y_disp = 0;
+            }
+        }
+
+        collision
+    }
+
+    /// Draws a 16x16 sprite (16 rows of 2 bytes each) at `(x, y)`, per
+    /// SUPER-CHIP's `DXY0`.
+    pub fn draw_sprite_16(&mut self, x: u8, y: u8, sprite: &[u16], clipping: bool) -> bool {
+        let width = self.width();
+        let height = self.height();
+        let mut collision = false;
+        let mut y_disp: usize = (y as usize) % height;
+
+        for &sprite_row in sprite {
+            let mut bit: u16 = 0b1000_0000_0000_0000;
+            let mut x_disp: usize = (x as usize) % width;
+
+            while bit > 0 {
+                if sprite_row & bit != 0 {
+                    collision |= self.toggle(x_disp, y_disp);
+                }
+                bit >>= 1;
+                x_disp += 1;
+                if x_disp == width {
+                    if clipping {
+                        break;
+                    }
+                    x_disp = 0;
+                }
+            }
+            y_disp += 1;
+            if y_disp == height {
+                if clipping {
+                    break;
+                }
+                y_disp = 0;
+            }
+        }
+
+        collision
+    }
+
+    fn toggle(&mut self, x: usize, y: usize) -> bool {
+        if self.pixels[y][x] {
+            self.pixels[y][x] = false;
+            true
+        } else {
+            self.pixels[y][x] = true;
+            false
+        }
+    }
+
+    /// Renders the current framebuffer into a fixed `MAX_WIDTH x MAX_HEIGHT`
+    /// buffer of 0xRRGGBB pixels, scaling low-res pixels up to 2x2 blocks.
+    pub fn render(&self, buffer: &mut [u32]) {
+        let scale = if self.hires { 1 } else { 2 };
+
+        for (y, row) in self.pixels.iter().enumerate() {
+            for (x, &pixel) in row.iter().enumerate() {
+                let color = if pixel { 0x00FFFFFF } else { 0x00000000 };
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let px = x * scale + dx;
+                        let py = y * scale + dy;
+                        buffer[py * MAX_WIDTH + px] = color;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn pixels(&self) -> &Vec<Vec<bool>> {
+        &self.pixels
+    }
+
+    pub fn set_pixels(&mut self, hires: bool, pixels: Vec<Vec<bool>>) {
+        self.hires = hires;
+        self.pixels = pixels;
+    }
+}
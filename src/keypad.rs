@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use minifb::{Key, Window};
+
+/// The 16-key CHIP-8 keypad, decoupled from `minifb` so the emulator core
+/// can run headless. `update` is the only place that touches the `Window`;
+/// everything else just reads the latched `pressed` state.
+pub struct Keypad {
+    key_map: HashMap<u8, Key>,
+    pressed: [bool; 16],
+}
+
+impl Keypad {
+    pub fn new() -> Self {
+        Keypad {
+            key_map: HashMap::from([
+                (0x1, Key::Key1),
+                (0x2, Key::Key2),
+                (0x3, Key::Key3),
+                (0xC, Key::Key4),
+                (0x4, Key::Q),
+                (0x5, Key::W),
+                (0x6, Key::E),
+                (0xD, Key::R),
+                (0x7, Key::A),
+                (0x8, Key::S),
+                (0x9, Key::D),
+                (0xE, Key::F),
+                (0xA, Key::Z),
+                (0x0, Key::X),
+                (0xB, Key::C),
+                (0xF, Key::V),
+            ]),
+            pressed: [false; 16],
+        }
+    }
+
+    /// Latches the current pressed state of every mapped key from the window.
+    pub fn update(&mut self, window: &Window) {
+        for (&code, &key) in self.key_map.iter() {
+            self.pressed[code as usize] = window.is_key_down(key);
+        }
+    }
+
+    pub fn is_pressed(&self, code: u8) -> bool {
+        self.pressed[code as usize]
+    }
+
+    /// Returns the first pressed key found, for `FX0A`'s blocking key wait.
+    pub fn wait_for_key(&self) -> Option<u8> {
+        self.pressed.iter().position(|&p| p).map(|code| code as u8)
+    }
+}
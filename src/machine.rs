@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+use std::io;
+
+use crate::audio::Audio;
+use crate::bus::Bus;
+use crate::cpu::{Cpu, Quirks};
+use crate::debugger::Debugger;
+use crate::snapshot::{Chip8State, CHECKPOINT_CAPACITY};
+
+/// Default number of CPU instructions executed per rendered frame.
+pub const DEFAULT_CYCLES_PER_FRAME: u32 = 600;
+/// Default display/timer refresh rate, in Hz.
+pub const DEFAULT_TARGET_FPS: f64 = 60.0;
+
+/// Top-level coordinator tying the CPU, its peripherals, and the buzzer
+/// together. This is the type a frontend (e.g. `main`'s `minifb` loop)
+/// drives; it has no rendering or windowing concerns of its own.
+pub struct Machine {
+    pub cpu: Cpu,
+    pub bus: Bus,
+    pub audio: Audio,
+    pub debugger: Debugger,
+    /// How many instructions `run_frame` executes per call. Tune this to
+    /// make a ROM run faster or slower without touching the timer rate.
+    pub cycles_per_frame: u32,
+    /// How often timers tick and the display is expected to refresh, in Hz.
+    pub target_fps: f64,
+    checkpoints: VecDeque<Chip8State>,
+}
+
+impl Machine {
+    pub fn new(quirks: Quirks) -> Self {
+        let mut bus = Bus::new();
+        bus.memory.load_fonts();
+
+        Machine {
+            cpu: Cpu::new(quirks),
+            bus,
+            audio: Audio::new(),
+            debugger: Debugger::new(),
+            cycles_per_frame: DEFAULT_CYCLES_PER_FRAME,
+            target_fps: DEFAULT_TARGET_FPS,
+            checkpoints: VecDeque::with_capacity(CHECKPOINT_CAPACITY),
+        }
+    }
+
+    pub fn load_rom(&mut self, filename: &str) -> Result<(), crate::memory::RomLoadError> {
+        self.bus.memory.load_rom(filename)
+    }
+
+    /// Fetches, decodes, and executes exactly one instruction, recording it
+    /// into the debugger's history and honoring any breakpoint it hits.
+    pub fn step(&mut self) {
+        let pc = self.cpu.pc;
+        let opcode = self.cpu.step(&mut self.bus);
+        self.debugger.record(pc, opcode);
+    }
+
+    /// Executes up to `cycles_per_frame` instructions, as long as the
+    /// debugger isn't holding the machine paused. Stops early the instant a
+    /// breakpoint hit pauses the debugger, rather than finishing the frame.
+    pub fn run_frame(&mut self) {
+        if self.debugger.paused {
+            return;
+        }
+        for _ in 0..self.cycles_per_frame {
+            self.step();
+            if self.debugger.paused {
+                break;
+            }
+        }
+    }
+
+    /// Decrements the delay and sound timers by one tick, and starts/stops
+    /// the buzzer to match whether `sound_timer` is still nonzero.
+    pub fn tick_timers(&mut self) {
+        if self.cpu.delay_timer > 0 {
+            self.cpu.delay_timer -= 1;
+        }
+
+        if self.cpu.sound_timer > 0 {
+            self.cpu.sound_timer -= 1;
+        }
+
+        self.audio.set_beeping(self.cpu.sound_timer > 0);
+    }
+
+    /// Captures the full machine state at this instant.
+    pub fn snapshot(&self) -> Chip8State {
+        Chip8State {
+            mem: self.bus.memory.mem,
+            pc: self.cpu.pc,
+            v: self.cpu.v,
+            i: self.cpu.i,
+            stack: self.cpu.stack,
+            sp: self.cpu.sp,
+            delay_timer: self.cpu.delay_timer,
+            sound_timer: self.cpu.sound_timer,
+            hires: self.bus.display.is_hires(),
+            display: self.bus.display.pixels().clone(),
+        }
+    }
+
+    /// Overwrites the machine's state with a previously captured snapshot.
+    pub fn restore(&mut self, state: &Chip8State) {
+        self.bus.memory.mem = state.mem;
+        self.cpu.pc = state.pc;
+        self.cpu.v = state.v;
+        self.cpu.i = state.i;
+        self.cpu.stack = state.stack;
+        self.cpu.sp = state.sp;
+        self.cpu.delay_timer = state.delay_timer;
+        self.cpu.sound_timer = state.sound_timer;
+        self.bus.display.set_pixels(state.hires, state.display.clone());
+    }
+
+    pub fn save_snapshot(&self, path: &str) -> io::Result<()> {
+        self.snapshot().save_to_file(path)
+    }
+
+    pub fn load_snapshot(&mut self, path: &str) -> io::Result<()> {
+        let state = Chip8State::load_from_file(path)?;
+        self.restore(&state);
+        Ok(())
+    }
+
+    /// Pushes the current state onto the bounded checkpoint history,
+    /// dropping the oldest checkpoint once `CHECKPOINT_CAPACITY` is reached.
+    pub fn checkpoint(&mut self) {
+        if self.checkpoints.len() == CHECKPOINT_CAPACITY {
+            self.checkpoints.pop_front();
+        }
+        self.checkpoints.push_back(self.snapshot());
+    }
+
+    /// Restores the most recent checkpoint, if any, popping it off the
+    /// history. Returns `false` when there is nothing to rewind to.
+    pub fn rewind(&mut self) -> bool {
+        match self.checkpoints.pop_back() {
+            Some(state) => {
+                self.restore(&state);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::fs;
+use std::io;
+
+const FONT_START: usize = 0x50;
+/// SUPER-CHIP's large 8x10 hex-digit font, loaded right after the small one.
+pub const BIG_FONT_START: usize = FONT_START + CHIP8_FONTSET.len();
+const ROM_START: usize = 0x200;
+/// Largest ROM that fits in the region after the font sets, `0x200..=0xFFF`.
+const ROM_MAX_LEN: usize = 0x1000 - ROM_START;
+
+const CHIP8_FONTSET: [u8; 80] = [
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0
+    0x20, 0x60, 0x20, 0x20, 0x70, // 1
+    0xF0, 0x10, 0xF0, 0x80, 0xF0, // 2
+    0xF0, 0x10, 0xF0, 0x10, 0xF0, // 3
+    0x90, 0x90, 0xF0, 0x10, 0x10, // 4
+    0xF0, 0x80, 0xF0, 0x10, 0xF0, // 5
+    0xF0, 0x80, 0xF0, 0x90, 0xF0, // 6
+    0xF0, 0x10, 0x20, 0x40, 0x40, // 7
+    0xF0, 0x90, 0xF0, 0x90, 0xF0, // 8
+    0xF0, 0x90, 0xF0, 0x10, 0xF0, // 9
+    0xF0, 0x90, 0xF0, 0x90, 0x90, // A
+    0xE0, 0x90, 0xE0, 0x90, 0xE0, // B
+    0xF0, 0x80, 0x80, 0x80, 0xF0, // C
+    0xE0, 0x90, 0x90, 0x90, 0xE0, // D
+    0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
+    0xF0, 0x80, 0xF0, 0x80, 0x80  // F
+];
+
+/// SUPER-CHIP's large hex-digit font: 16 digits, 10 bytes (8x10 pixels) each.
+const BIG_FONTSET: [u8; 160] = [
+    0x3C, 0x7E, 0xE7, 0xC3, 0xC3, 0xC3, 0xC3, 0xE7, 0x7E, 0x3C, // 0
+    0x18, 0x38, 0x58, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, // 1
+    0x3E, 0x7F, 0xC3, 0x06, 0x0C, 0x18, 0x30, 0x60, 0xFF, 0xFF, // 2
+    0x3C, 0x7E, 0xC3, 0x03, 0x0E, 0x0E, 0x03, 0xC3, 0x7E, 0x3C, // 3
+    0x06, 0x0E, 0x1E, 0x36, 0x66, 0xC6, 0xFF, 0xFF, 0x06, 0x06, // 4
+    0xFF, 0xFF, 0xC0, 0xFE, 0xFF, 0x03, 0x03, 0xC3, 0x7E, 0x3C, // 5
+    0x3E, 0x7C, 0xC0, 0xFC, 0xFE, 0xC3, 0xC3, 0xC3, 0x7E, 0x3C, // 6
+    0xFF, 0xFF, 0x03, 0x06, 0x0C, 0x18, 0x30, 0x30, 0x30, 0x30, // 7
+    0x3C, 0x7E, 0xC3, 0xC3, 0x7E, 0x7E, 0xC3, 0xC3, 0x7E, 0x3C, // 8
+    0x3C, 0x7E, 0xC3, 0xC3, 0x7F, 0x3F, 0x03, 0x03, 0x3E, 0x7C, // 9
+    0x18, 0x3C, 0x66, 0xC3, 0xC3, 0xFF, 0xFF, 0xC3, 0xC3, 0xC3, // A
+    0xFC, 0xFE, 0xC3, 0xC3, 0xFE, 0xFE, 0xC3, 0xC3, 0xFE, 0xFC, // B
+    0x3E, 0x7F, 0xC3, 0xC0, 0xC0, 0xC0, 0xC0, 0xC3, 0x7F, 0x3E, // C
+    0xFC, 0xFE, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xFE, 0xFC, // D
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFC, 0xC0, 0xC0, 0xFF, 0xFF, // E
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFC, 0xC0, 0xC0, 0xC0, 0xC0, // F
+];
+
+/// The CHIP-8 address space: 4 KiB of flat memory shared by the font sets,
+/// loaded ROM, and whatever the running program writes via `FX33`/`FX55`.
+pub struct Memory {
+    pub mem: [u8; 4096],
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Memory { mem: [0; 4096] }
+    }
+
+    pub fn load_fonts(&mut self) {
+        self.mem[FONT_START..FONT_START + CHIP8_FONTSET.len()].clone_from_slice(&CHIP8_FONTSET);
+        self.mem[BIG_FONT_START..BIG_FONT_START + BIG_FONTSET.len()].clone_from_slice(&BIG_FONTSET);
+    }
+
+    /// Loads a ROM at `0x200`, rejecting anything that won't fit in the
+    /// `0x200..=0xFFF` region or isn't a whole number of opcodes, instead of
+    /// panicking inside `clone_from_slice`.
+    pub fn load_rom(&mut self, filename: &str) -> Result<(), RomLoadError> {
+        let rom: Vec<u8> = fs::read(filename)?;
+
+        if rom.is_empty() {
+            return Err(RomLoadError::Empty);
+        }
+        if rom.len() > ROM_MAX_LEN {
+            return Err(RomLoadError::TooLarge { size: rom.len(), max: ROM_MAX_LEN });
+        }
+        if rom.len() % 2 != 0 {
+            return Err(RomLoadError::OddLength { size: rom.len() });
+        }
+
+        self.mem[ROM_START..ROM_START + rom.len()].clone_from_slice(&rom);
+        Ok(())
+    }
+}
+
+/// Why a ROM file couldn't be loaded.
+#[derive(Debug)]
+pub enum RomLoadError {
+    Io(io::Error),
+    Empty,
+    TooLarge { size: usize, max: usize },
+    OddLength { size: usize },
+}
+
+impl fmt::Display for RomLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RomLoadError::Io(e) => write!(f, "unable to read ROM: {}", e),
+            RomLoadError::Empty => write!(f, "ROM file is empty"),
+            RomLoadError::TooLarge { size, max } => write!(
+                f,
+                "ROM is {} bytes but only {} bytes are available (0x200..=0xFFF)",
+                size, max
+            ),
+            RomLoadError::OddLength { size } => write!(
+                f,
+                "ROM is {} bytes, which is not a whole number of 2-byte opcodes",
+                size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RomLoadError {}
+
+impl From<io::Error> for RomLoadError {
+    fn from(e: io::Error) -> Self {
+        RomLoadError::Io(e)
+    }
+}
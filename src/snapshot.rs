@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+
+use crate::display::{HIRES_HEIGHT, HIRES_WIDTH, LORES_HEIGHT, LORES_WIDTH};
+
+/// Bounded history of automatic checkpoints kept for the rewind key.
+pub const CHECKPOINT_CAPACITY: usize = 10;
+
+/// A full, self-contained copy of everything that defines the machine's
+/// behavior at one instant: memory, CPU registers, the stack, both timers,
+/// and the framebuffer (at whichever resolution was active). Used for
+/// save/restore and for the rewind checkpoints.
+#[derive(Clone)]
+pub struct Chip8State {
+    pub mem: [u8; 4096],
+    pub pc: u16,
+    pub v: [u8; 16],
+    pub i: u16,
+    pub stack: [u16; 16],
+    pub sp: u8,
+    pub delay_timer: u8,
+    pub sound_timer: u8,
+    pub hires: bool,
+    pub display: Vec<Vec<bool>>,
+}
+
+impl Chip8State {
+    /// Packs the state into a flat byte buffer so it can be written to disk
+    /// without pulling in a serialization crate.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4096 + 2 + 16 + 2 + 32 + 1 + 1 + 1 + 1 + HIRES_WIDTH * HIRES_HEIGHT);
+        bytes.extend_from_slice(&self.mem);
+        bytes.extend_from_slice(&self.pc.to_le_bytes());
+        bytes.extend_from_slice(&self.v);
+        bytes.extend_from_slice(&self.i.to_le_bytes());
+        for slot in &self.stack {
+            bytes.extend_from_slice(&slot.to_le_bytes());
+        }
+        bytes.push(self.sp);
+        bytes.push(self.delay_timer);
+        bytes.push(self.sound_timer);
+        bytes.push(self.hires as u8);
+        for row in &self.display {
+            for &pixel in row {
+                bytes.push(pixel as u8);
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut pos = 0;
+        let mut take = |len: usize| {
+            let slice = &bytes[pos..pos + len];
+            pos += len;
+            slice
+        };
+
+        let mut mem = [0u8; 4096];
+        mem.copy_from_slice(take(4096));
+
+        let pc = u16::from_le_bytes(take(2).try_into().unwrap());
+
+        let mut v = [0u8; 16];
+        v.copy_from_slice(take(16));
+
+        let i = u16::from_le_bytes(take(2).try_into().unwrap());
+
+        let mut stack = [0u16; 16];
+        for slot in stack.iter_mut() {
+            *slot = u16::from_le_bytes(take(2).try_into().unwrap());
+        }
+
+        let sp = take(1)[0];
+        let delay_timer = take(1)[0];
+        let sound_timer = take(1)[0];
+        let hires = take(1)[0] != 0;
+
+        let (width, height) = if hires { (HIRES_WIDTH, HIRES_HEIGHT) } else { (LORES_WIDTH, LORES_HEIGHT) };
+        let mut display = vec![vec![false; width]; height];
+        for row in display.iter_mut() {
+            for pixel in row.iter_mut() {
+                *pixel = take(1)[0] != 0;
+            }
+        }
+
+        Chip8State { mem, pc, v, i, stack, sp, delay_timer, sound_timer, hires, display }
+    }
+
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load_from_file(path: &str) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Ok(Chip8State::from_bytes(&bytes))
+    }
+}